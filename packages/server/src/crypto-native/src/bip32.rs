@@ -0,0 +1,235 @@
+//! BIP32-Ed25519 hierarchical deterministic key derivation (Khovratovich-Law scheme).
+//!
+//! Derives child Ed25519 signing keys from an extended private key
+//! `(kL, kR, chainCode)` so callers can generate per-conversation or
+//! per-device identity keys from a single backup seed.
+
+use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek::scalar::Scalar;
+use ed25519_dalek::{ExpandedSecretKey, PublicKey};
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// An extended Ed25519 private key as defined by the BIP32-Ed25519 spec:
+/// a 64-byte expanded scalar `(kL, kR)` plus a 32-byte chain code.
+pub struct ExtendedSecretKey {
+    pub kl: [u8; 32],
+    pub kr: [u8; 32],
+    pub chain_code: [u8; 32],
+}
+
+impl ExtendedSecretKey {
+    /// Build an extended key from a raw 96-byte buffer: `kL (32) || kR (32) || chainCode (32)`.
+    pub fn from_bytes(bytes: &[u8; 96]) -> Self {
+        let mut kl = [0u8; 32];
+        let mut kr = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        kl.copy_from_slice(&bytes[0..32]);
+        kr.copy_from_slice(&bytes[32..64]);
+        chain_code.copy_from_slice(&bytes[64..96]);
+        ExtendedSecretKey { kl, kr, chain_code }
+    }
+
+    /// The compressed Edwards public point `A = kL * B`.
+    pub fn public_key(&self) -> [u8; 32] {
+        let scalar = Scalar::from_bits(self.kl);
+        (&scalar * &ED25519_BASEPOINT_TABLE).compress().to_bytes()
+    }
+
+    /// Serialize `kL || kR` in `ed25519_dalek::ExpandedSecretKey::from_bytes` layout
+    /// (scalar || nonce). This is *not* a `Keypair::to_bytes()` seed: the derivation
+    /// never produces a 32-byte seed to re-hash, so signing a child key must go
+    /// through `sign_with_expanded_key` rather than `Keypair::from_bytes(..).sign(..)`.
+    pub fn expanded_secret_bytes(&self) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        out[0..32].copy_from_slice(&self.kl);
+        out[32..64].copy_from_slice(&self.kr);
+        out
+    }
+}
+
+/// Sign with the expanded `kL || kR` bytes a child key derives to, rather than
+/// the seed-and-clamp path `Keypair::sign` uses — there is no seed to re-hash.
+pub fn sign_with_expanded_key(
+    expanded_secret_bytes: &[u8; 64],
+    public_key_bytes: &[u8; 32],
+    message: &[u8],
+) -> Result<[u8; 64], String> {
+    let expanded_secret =
+        ExpandedSecretKey::from_bytes(expanded_secret_bytes).map_err(|e| e.to_string())?;
+    let public_key = PublicKey::from_bytes(public_key_bytes).map_err(|e| e.to_string())?;
+    Ok(expanded_secret.sign(message, &public_key).to_bytes())
+}
+
+fn add_256(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut carry: u16 = 0;
+    for i in 0..32 {
+        let sum = a[i] as u16 + b[i] as u16 + carry;
+        out[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    out
+}
+
+fn mul8_256(z_lo: &[u8; 28]) -> [u8; 32] {
+    let mut extended = [0u8; 32];
+    extended[0..28].copy_from_slice(z_lo);
+    let mut out = [0u8; 32];
+    let mut carry: u16 = 0;
+    for i in 0..32 {
+        let shifted = (extended[i] as u16) << 3 | carry;
+        out[i] = shifted as u8;
+        carry = shifted >> 8;
+    }
+    out
+}
+
+/// Derive a non-hardened or hardened child extended key at the given index.
+///
+/// Hardened indices (`index >= 0x8000_0000`) use the parent's private key bytes
+/// in the HMAC input per the BIP32-Ed25519 spec; non-hardened indices use the
+/// parent's compressed public point.
+pub fn derive_child(parent: &ExtendedSecretKey, index: u32) -> ExtendedSecretKey {
+    let hardened = index >= 0x8000_0000;
+    let index_le = index.to_le_bytes();
+
+    let mut z_input = Vec::with_capacity(69);
+    let mut cc_input = Vec::with_capacity(69);
+    if hardened {
+        z_input.push(0x00);
+        z_input.extend_from_slice(&parent.kl);
+        z_input.extend_from_slice(&parent.kr);
+        cc_input.push(0x01);
+        cc_input.extend_from_slice(&parent.kl);
+        cc_input.extend_from_slice(&parent.kr);
+    } else {
+        let a_parent = parent.public_key();
+        z_input.push(0x02);
+        z_input.extend_from_slice(&a_parent);
+        cc_input.push(0x03);
+        cc_input.extend_from_slice(&a_parent);
+    }
+    z_input.extend_from_slice(&index_le);
+    cc_input.extend_from_slice(&index_le);
+
+    let mut z_mac = HmacSha512::new_from_slice(&parent.chain_code).expect("HMAC accepts any key length");
+    z_mac.update(&z_input);
+    let z = z_mac.finalize().into_bytes();
+
+    let mut z_lo = [0u8; 28];
+    z_lo.copy_from_slice(&z[0..28]);
+    let mut z_hi = [0u8; 32];
+    z_hi.copy_from_slice(&z[32..64]);
+
+    let kl_child = add_256(&parent.kl, &mul8_256(&z_lo));
+    let kr_child = add_256(&z_hi, &parent.kr);
+
+    let mut cc_mac = HmacSha512::new_from_slice(&parent.chain_code).expect("HMAC accepts any key length");
+    cc_mac.update(&cc_input);
+    let cc_out = cc_mac.finalize().into_bytes();
+    let mut chain_code_child = [0u8; 32];
+    chain_code_child.copy_from_slice(&cc_out[32..64]);
+
+    ExtendedSecretKey {
+        kl: kl_child,
+        kr: kr_child,
+        chain_code: chain_code_child,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn master_key() -> ExtendedSecretKey {
+        let mut seed = [0u8; 96];
+        for (i, byte) in seed.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        // Clamp kL like a real BIP32-Ed25519 root key would be.
+        seed[0] &= 0xf8;
+        seed[31] &= 0x1f;
+        seed[31] |= 0x40;
+        ExtendedSecretKey::from_bytes(&seed)
+    }
+
+    #[test]
+    fn derivation_is_deterministic() {
+        let master = master_key();
+        let child_a = derive_child(&master, 1);
+        let child_b = derive_child(&master, 1);
+        assert_eq!(child_a.public_key(), child_b.public_key());
+        assert_eq!(child_a.chain_code, child_b.chain_code);
+    }
+
+    #[test]
+    fn child_signature_verifies_against_its_own_public_key() {
+        let master = master_key();
+        let child = derive_child(&master, 0);
+
+        let message = b"private-messaging handshake";
+        let signature_bytes =
+            sign_with_expanded_key(&child.expanded_secret_bytes(), &child.public_key(), message)
+                .expect("signing with the derived expanded key must succeed");
+
+        let public_key = PublicKey::from_bytes(&child.public_key()).unwrap();
+        let signature = ed25519_dalek::Signature::from_bytes(&signature_bytes).unwrap();
+        assert!(ed25519_dalek::Verifier::verify(&public_key, message, &signature).is_ok());
+    }
+
+    #[test]
+    fn hardened_and_non_hardened_children_diverge() {
+        let master = master_key();
+        let non_hardened = derive_child(&master, 0);
+        let hardened = derive_child(&master, 0x8000_0000);
+        assert_ne!(non_hardened.public_key(), hardened.public_key());
+    }
+
+    fn hex32(s: &str) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).unwrap();
+        }
+        out
+    }
+
+    /// Cross-checked against an independent pure-Python HMAC-SHA512 +
+    /// Edwards-point-multiplication reimplementation of this same derivation,
+    /// run against the `master_key()` test seed above.
+    #[test]
+    fn non_hardened_child_matches_independent_reimplementation() {
+        let master = master_key();
+        let child = derive_child(&master, 1);
+
+        assert_eq!(
+            child.kl,
+            hex32("68f9087e833cabb653dd5a47be82f79cee6290d2e4bad40406e725ee1c1d1e5f")
+        );
+        assert_eq!(
+            child.kr,
+            hex32("677047eb6e81d840ef0a17ec2dba4e8f2d9b5c9ec64d6427253611bcbc4268ac")
+        );
+        assert_eq!(
+            child.chain_code,
+            hex32("21dfd0d6d2c737dd6f2cf4543df89fe20c6a458977b3d1ff5f755d49695422f9")
+        );
+        assert_eq!(
+            child.public_key(),
+            hex32("35830ce81021f89a75e0af0d305966965aa587947d8ea3bb1c4ff9f2ef4c0da6")
+        );
+    }
+
+    #[test]
+    fn hardened_child_public_key_matches_independent_reimplementation() {
+        let master = master_key();
+        let child = derive_child(&master, 0x8000_0000);
+
+        assert_eq!(
+            child.public_key(),
+            hex32("29614b36f03f3b2448f79aed59884a63c6683251460a1fc764a61a9b176c851d")
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Minimal DER encoder/decoder and PKCS#8 / SubjectPublicKeyInfo wrapping for the
+//! raw Ed25519 and X25519 keys this crate hands out, so they interoperate with
+//! OpenSSL, WebCrypto `importKey`, and standard key files.
+
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OBJECT_ID: u8 = 0x06;
+
+pub const OID_ED25519: [u8; 3] = [0x2b, 0x65, 0x70];
+pub const OID_X25519: [u8; 3] = [0x2b, 0x65, 0x6e];
+
+fn encode_len(len: usize, out: &mut Vec<u8>) {
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let significant: Vec<u8> = bytes.iter().copied().skip_while(|b| *b == 0).collect();
+        out.push(0x80 | significant.len() as u8);
+        out.extend_from_slice(&significant);
+    }
+}
+
+fn encode_tlv(tag: u8, value: &[u8], out: &mut Vec<u8>) {
+    out.push(tag);
+    encode_len(value.len(), out);
+    out.extend_from_slice(value);
+}
+
+fn encode_sequence(children: &[u8], out: &mut Vec<u8>) {
+    encode_tlv(TAG_SEQUENCE, children, out);
+}
+
+/// Read one tag-length-value node, returning `(tag, value, rest)`.
+fn read_tlv(input: &[u8]) -> Result<(u8, &[u8], &[u8]), String> {
+    if input.len() < 2 {
+        return Err("DER: truncated input".into());
+    }
+    let tag = input[0];
+    let (len, header_len) = if input[1] & 0x80 == 0 {
+        (input[1] as usize, 2usize)
+    } else {
+        let num_len_bytes = (input[1] & 0x7f) as usize;
+        if input.len() < 2 + num_len_bytes {
+            return Err("DER: truncated length".into());
+        }
+        let mut len = 0usize;
+        for &b in &input[2..2 + num_len_bytes] {
+            len = (len << 8) | b as usize;
+        }
+        (len, 2 + num_len_bytes)
+    };
+    if input.len() < header_len + len {
+        return Err("DER: truncated value".into());
+    }
+    let value = &input[header_len..header_len + len];
+    let rest = &input[header_len + len..];
+    Ok((tag, value, rest))
+}
+
+fn expect_tag<'a>(input: &'a [u8], tag: u8) -> Result<(&'a [u8], &'a [u8]), String> {
+    let (found, value, rest) = read_tlv(input)?;
+    if found != tag {
+        return Err(format!("DER: expected tag {:#x}, found {:#x}", tag, found));
+    }
+    Ok((value, rest))
+}
+
+/// Build a SubjectPublicKeyInfo DER document: `SEQUENCE { SEQUENCE { OID }, BIT STRING (0x00 || key) }`.
+pub fn encode_spki(oid: &[u8; 3], public_key: &[u8]) -> Vec<u8> {
+    let mut alg_id = Vec::new();
+    let mut oid_tlv = Vec::new();
+    encode_tlv(TAG_OBJECT_ID, oid, &mut oid_tlv);
+    encode_sequence(&oid_tlv, &mut alg_id);
+
+    let mut bit_string_value = Vec::with_capacity(public_key.len() + 1);
+    bit_string_value.push(0x00);
+    bit_string_value.extend_from_slice(public_key);
+    let mut bit_string = Vec::new();
+    encode_tlv(TAG_BIT_STRING, &bit_string_value, &mut bit_string);
+
+    let mut body = Vec::new();
+    body.extend_from_slice(&alg_id);
+    body.extend_from_slice(&bit_string);
+
+    let mut out = Vec::new();
+    encode_sequence(&body, &mut out);
+    out
+}
+
+/// Parse a SubjectPublicKeyInfo DER document, validating the algorithm OID.
+pub fn decode_spki(der: &[u8], expected_oid: &[u8; 3]) -> Result<Vec<u8>, String> {
+    let (body, _) = expect_tag(der, TAG_SEQUENCE)?;
+    let (alg_id, rest) = expect_tag(body, TAG_SEQUENCE)?;
+    let (oid, _) = expect_tag(alg_id, TAG_OBJECT_ID)?;
+    if oid != expected_oid {
+        return Err("DER: unexpected algorithm OID".into());
+    }
+    let (bit_string, _) = expect_tag(rest, TAG_BIT_STRING)?;
+    if bit_string.is_empty() || bit_string[0] != 0x00 {
+        return Err("DER: unexpected BIT STRING unused-bits count".into());
+    }
+    Ok(bit_string[1..].to_vec())
+}
+
+/// Build a PKCS#8 v1 DER document: `SEQUENCE { INTEGER 0, SEQUENCE { OID }, OCTET STRING (OCTET STRING(seed)) }`.
+pub fn encode_pkcs8(oid: &[u8; 3], seed: &[u8]) -> Vec<u8> {
+    let mut version = Vec::new();
+    encode_tlv(TAG_INTEGER, &[0x00], &mut version);
+
+    let mut oid_tlv = Vec::new();
+    encode_tlv(TAG_OBJECT_ID, oid, &mut oid_tlv);
+    let mut alg_id = Vec::new();
+    encode_sequence(&oid_tlv, &mut alg_id);
+
+    let mut inner_octet_string = Vec::new();
+    encode_tlv(TAG_OCTET_STRING, seed, &mut inner_octet_string);
+    let mut outer_octet_string = Vec::new();
+    encode_tlv(TAG_OCTET_STRING, &inner_octet_string, &mut outer_octet_string);
+
+    let mut body = Vec::new();
+    body.extend_from_slice(&version);
+    body.extend_from_slice(&alg_id);
+    body.extend_from_slice(&outer_octet_string);
+
+    let mut out = Vec::new();
+    encode_sequence(&body, &mut out);
+    out
+}
+
+/// Parse a PKCS#8 v1 DER document, validating the algorithm OID.
+pub fn decode_pkcs8(der: &[u8], expected_oid: &[u8; 3]) -> Result<Vec<u8>, String> {
+    let (body, _) = expect_tag(der, TAG_SEQUENCE)?;
+    let (version, rest) = expect_tag(body, TAG_INTEGER)?;
+    if version != [0x00] {
+        return Err("DER: unsupported PKCS#8 version".into());
+    }
+    let (alg_id, rest) = expect_tag(rest, TAG_SEQUENCE)?;
+    let (oid, _) = expect_tag(alg_id, TAG_OBJECT_ID)?;
+    if oid != expected_oid {
+        return Err("DER: unexpected algorithm OID".into());
+    }
+    let (outer_octet_string, _) = expect_tag(rest, TAG_OCTET_STRING)?;
+    let (inner_octet_string, _) = expect_tag(outer_octet_string, TAG_OCTET_STRING)?;
+    Ok(inner_octet_string.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spki_round_trips() {
+        let public_key = [7u8; 32];
+        let der = encode_spki(&OID_ED25519, &public_key);
+        let decoded = decode_spki(&der, &OID_ED25519).expect("decode must succeed");
+        assert_eq!(decoded, public_key.to_vec());
+    }
+
+    #[test]
+    fn spki_rejects_mismatched_oid() {
+        let public_key = [7u8; 32];
+        let der = encode_spki(&OID_ED25519, &public_key);
+        assert!(decode_spki(&der, &OID_X25519).is_err());
+    }
+
+    #[test]
+    fn pkcs8_round_trips() {
+        let seed = [9u8; 32];
+        let der = encode_pkcs8(&OID_X25519, &seed);
+        let decoded = decode_pkcs8(&der, &OID_X25519).expect("decode must succeed");
+        assert_eq!(decoded, seed.to_vec());
+    }
+
+    #[test]
+    fn pkcs8_rejects_mismatched_oid() {
+        let seed = [9u8; 32];
+        let der = encode_pkcs8(&OID_X25519, &seed);
+        assert!(decode_pkcs8(&der, &OID_ED25519).is_err());
+    }
+
+    #[test]
+    fn pkcs8_rejects_truncated_input() {
+        let seed = [9u8; 32];
+        let der = encode_pkcs8(&OID_ED25519, &seed);
+        assert!(decode_pkcs8(&der[..der.len() - 5], &OID_ED25519).is_err());
+    }
+}
@@ -0,0 +1,388 @@
+//! EDHOC-style three-message mutually authenticated key exchange.
+//!
+//! - Message 1 (initiator -> responder): ephemeral X25519 public key `G_X`
+//!   plus a connection id.
+//! - Message 2 (responder -> initiator): ephemeral `G_Y` plus an
+//!   AEAD-encrypted block carrying the responder's Ed25519 identity and a
+//!   signature over the transcript `H(msg1 || G_Y)`.
+//! - Message 3 (initiator -> responder): an AEAD-encrypted block carrying
+//!   the initiator's Ed25519 identity and a signature over the transcript
+//!   `H(msg1 || msg2)` (message 2 itself begins with `G_Y`).
+//!
+//! Both sides derive the same symmetric transport key
+//! `HKDF-Expand(PRK, "traffic", 32)` from the shared `PRK`, and the whole
+//! state machine is carried across calls in a boxed `HandshakeState`.
+
+use crate::rand_compat::RingRng;
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use ed25519_dalek::{Keypair, PublicKey as EdPublicKey, Signature, Signer, Verifier};
+use hkdf::Hkdf;
+use neon::types::Finalize;
+use ring::rand::{SecureRandom, SystemRandom};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use x25519_dalek::{PublicKey as XPublicKey, StaticSecret};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Boxed handshake state, threaded across `handshakeInit` / `handshakeRespond`
+/// / `handshakeFinalize` calls.
+pub struct HandshakeState(RefCell<Option<Role>>);
+
+impl Finalize for HandshakeState {}
+
+enum Role {
+    InitiatorAwaitingMessage2 {
+        ephemeral_secret: StaticSecret,
+        message1: Vec<u8>,
+    },
+    ResponderAwaitingMessage3 {
+        prk: Zeroizing<[u8; 32]>,
+        message1: Vec<u8>,
+        message2: Vec<u8>,
+    },
+}
+
+impl Drop for Role {
+    fn drop(&mut self) {
+        if let Role::InitiatorAwaitingMessage2 { ephemeral_secret, .. } = self {
+            ephemeral_secret.zeroize();
+        }
+    }
+}
+
+/// The outcome of a completed handshake: the derived transport key and the
+/// authenticated peer identity.
+pub struct Completed {
+    pub transport_key: [u8; 32],
+    pub peer_identity: [u8; 32],
+}
+
+impl HandshakeState {
+    /// `Some(true)` if awaiting message 2 (initiator side), `Some(false)` if
+    /// awaiting message 3 (responder side), `None` if already consumed.
+    pub fn is_awaiting_message2(&self) -> Option<bool> {
+        self.0
+            .borrow()
+            .as_ref()
+            .map(|role| matches!(role, Role::InitiatorAwaitingMessage2 { .. }))
+    }
+}
+
+fn transcript_hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let digest = hasher.finalize();
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&digest);
+    out
+}
+
+fn expand(prk: &[u8; 32], info: &[u8], len: usize) -> Vec<u8> {
+    let hk = Hkdf::<Sha256>::from_prk(prk).expect("PRK is always 32 bytes");
+    let mut okm = vec![0u8; len];
+    hk.expand(info, &mut okm).expect("HKDF expand with a bounded length cannot fail");
+    okm
+}
+
+fn aead_seal(key: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let mut nonce_bytes = [0u8; 24];
+    SystemRandom::new()
+        .fill(&mut nonce_bytes)
+        .map_err(|_| "Failed to generate nonce".to_string())?;
+
+    let cipher = XChaCha20Poly1305::new(chacha20poly1305::Key::from_slice(key));
+    let nonce = XNonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, Payload { msg: plaintext, aad })
+        .map_err(|_| "Handshake block encryption failed".to_string())?;
+
+    let mut out = Vec::with_capacity(24 + ciphertext.len());
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+fn aead_open(key: &[u8], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, String> {
+    if sealed.len() < 24 {
+        return Err("Handshake block is too short to contain a nonce".to_string());
+    }
+    let (nonce_bytes, ciphertext) = sealed.split_at(24);
+    let cipher = XChaCha20Poly1305::new(chacha20poly1305::Key::from_slice(key));
+    let nonce = XNonce::from_slice(nonce_bytes);
+    cipher
+        .decrypt(nonce, Payload { msg: ciphertext, aad })
+        .map_err(|_| "Handshake block authentication failed".to_string())
+}
+
+/// Message 1: the initiator generates an ephemeral X25519 key pair and sends
+/// `G_X || connection_id`.
+pub fn init(connection_id: &[u8]) -> (HandshakeState, Vec<u8>) {
+    let ephemeral_secret = StaticSecret::new(&mut RingRng);
+    let ephemeral_public = XPublicKey::from(&ephemeral_secret);
+
+    let mut message1 = Vec::with_capacity(32 + connection_id.len());
+    message1.extend_from_slice(ephemeral_public.as_bytes());
+    message1.extend_from_slice(connection_id);
+
+    let state = HandshakeState(RefCell::new(Some(Role::InitiatorAwaitingMessage2 {
+        ephemeral_secret,
+        message1: message1.clone(),
+    })));
+
+    (state, message1)
+}
+
+/// Message 2: the responder consumes message 1, derives the handshake
+/// secret, and returns its ephemeral public key plus an encrypted block
+/// authenticating its identity.
+pub fn respond(
+    message1: &[u8],
+    responder_identity: &Keypair,
+) -> Result<(HandshakeState, Vec<u8>), String> {
+    if message1.len() < 32 {
+        return Err("Message 1 is too short to contain G_X".to_string());
+    }
+    let mut g_x_bytes = [0u8; 32];
+    g_x_bytes.copy_from_slice(&message1[0..32]);
+    let g_x = XPublicKey::from(g_x_bytes);
+
+    let ephemeral_secret = StaticSecret::new(&mut RingRng);
+    let g_y = XPublicKey::from(&ephemeral_secret);
+
+    let dh1 = ephemeral_secret.diffie_hellman(&g_x);
+    let (prk, _) = Hkdf::<Sha256>::extract(Some(&[]), dh1.as_bytes());
+    let mut prk_bytes = [0u8; 32];
+    prk_bytes.copy_from_slice(&prk);
+
+    let transcript2 = transcript_hash(&[message1, g_y.as_bytes()]);
+
+    let mut identity_block = Vec::with_capacity(32 + 64);
+    identity_block.extend_from_slice(responder_identity.public.as_bytes());
+    let signature = responder_identity.sign(&transcript2);
+    identity_block.extend_from_slice(&signature.to_bytes());
+
+    let m2_key = expand(&prk_bytes, b"m2", 32);
+    let encrypted_block = aead_seal(&m2_key, &transcript2, &identity_block)?;
+
+    let mut message2 = Vec::with_capacity(32 + encrypted_block.len());
+    message2.extend_from_slice(g_y.as_bytes());
+    message2.extend_from_slice(&encrypted_block);
+
+    let state = HandshakeState(RefCell::new(Some(Role::ResponderAwaitingMessage3 {
+        prk: Zeroizing::new(prk_bytes),
+        message1: message1.to_vec(),
+        message2: message2.clone(),
+    })));
+    prk_bytes.zeroize();
+
+    Ok((state, message2))
+}
+
+/// Message 3 (initiator side): verify the responder's signature from
+/// message 2, then produce the initiator's own encrypted identity block and
+/// derive the shared transport key.
+pub fn finalize_initiator(
+    state: &HandshakeState,
+    message2: &[u8],
+    initiator_identity: &Keypair,
+) -> Result<(Vec<u8>, Completed), String> {
+    let role = state
+        .0
+        .borrow_mut()
+        .take()
+        .ok_or_else(|| "Handshake state already consumed".to_string())?;
+
+    let (ephemeral_secret, message1) = match &role {
+        Role::InitiatorAwaitingMessage2 { ephemeral_secret, message1 } => (ephemeral_secret, message1),
+        Role::ResponderAwaitingMessage3 { .. } => {
+            return Err("Handshake state is not awaiting message 2".to_string())
+        }
+    };
+
+    if message2.len() < 32 {
+        return Err("Message 2 is too short to contain G_Y".to_string());
+    }
+    let mut g_y_bytes = [0u8; 32];
+    g_y_bytes.copy_from_slice(&message2[0..32]);
+    let g_y = XPublicKey::from(g_y_bytes);
+    let encrypted_block = &message2[32..];
+
+    let dh1 = ephemeral_secret.diffie_hellman(&g_y);
+    let (prk, _) = Hkdf::<Sha256>::extract(Some(&[]), dh1.as_bytes());
+    let mut prk_bytes = [0u8; 32];
+    prk_bytes.copy_from_slice(&prk);
+
+    let transcript2 = transcript_hash(&[&message1, g_y.as_bytes()]);
+
+    let m2_key = expand(&prk_bytes, b"m2", 32);
+    let identity_block = aead_open(&m2_key, &transcript2, encrypted_block)?;
+    if identity_block.len() != 32 + 64 {
+        return Err("Malformed message 2 identity block".to_string());
+    }
+    let mut responder_public_bytes = [0u8; 32];
+    responder_public_bytes.copy_from_slice(&identity_block[0..32]);
+    let responder_public = EdPublicKey::from_bytes(&responder_public_bytes)
+        .map_err(|_| "Invalid responder identity key".to_string())?;
+    let responder_signature = Signature::from_bytes(&identity_block[32..96])
+        .map_err(|_| "Invalid responder signature".to_string())?;
+    responder_public
+        .verify(&transcript2, &responder_signature)
+        .map_err(|_| "Responder signature verification failed".to_string())?;
+
+    let transcript3 = transcript_hash(&[&message1, message2]);
+
+    let mut own_identity_block = Vec::with_capacity(32 + 64);
+    own_identity_block.extend_from_slice(initiator_identity.public.as_bytes());
+    let own_signature = initiator_identity.sign(&transcript3);
+    own_identity_block.extend_from_slice(&own_signature.to_bytes());
+
+    let m3_key = expand(&prk_bytes, b"m3", 32);
+    let message3 = aead_seal(&m3_key, &transcript3, &own_identity_block)?;
+
+    let transport_key_bytes = expand(&prk_bytes, b"traffic", 32);
+    let mut transport_key = [0u8; 32];
+    transport_key.copy_from_slice(&transport_key_bytes);
+    prk_bytes.zeroize();
+
+    Ok((
+        message3,
+        Completed {
+            transport_key,
+            peer_identity: responder_public_bytes,
+        },
+    ))
+}
+
+/// Message 3 (responder side): verify the initiator's signature and derive
+/// the shared transport key. There is no further message to send.
+pub fn finalize_responder(state: &HandshakeState, message3: &[u8]) -> Result<Completed, String> {
+    let role = state
+        .0
+        .borrow_mut()
+        .take()
+        .ok_or_else(|| "Handshake state already consumed".to_string())?;
+
+    let (prk, message1, message2) = match &role {
+        Role::ResponderAwaitingMessage3 { prk, message1, message2 } => (prk, message1, message2),
+        Role::InitiatorAwaitingMessage2 { .. } => {
+            return Err("Handshake state is not awaiting message 3".to_string())
+        }
+    };
+
+    let transcript3 = transcript_hash(&[&message1, &message2]);
+
+    let m3_key = expand(&prk, b"m3", 32);
+    let identity_block = aead_open(&m3_key, &transcript3, message3)?;
+    if identity_block.len() != 32 + 64 {
+        return Err("Malformed message 3 identity block".to_string());
+    }
+    let mut initiator_public_bytes = [0u8; 32];
+    initiator_public_bytes.copy_from_slice(&identity_block[0..32]);
+    let initiator_public = EdPublicKey::from_bytes(&initiator_public_bytes)
+        .map_err(|_| "Invalid initiator identity key".to_string())?;
+    let initiator_signature = Signature::from_bytes(&identity_block[32..96])
+        .map_err(|_| "Invalid initiator signature".to_string())?;
+    initiator_public
+        .verify(&transcript3, &initiator_signature)
+        .map_err(|_| "Initiator signature verification failed".to_string())?;
+
+    let transport_key_bytes = expand(&prk, b"traffic", 32);
+    let mut transport_key = [0u8; 32];
+    transport_key.copy_from_slice(&transport_key_bytes);
+
+    Ok(Completed {
+        transport_key,
+        peer_identity: initiator_public_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_handshake_converges_on_matching_transport_key_and_identities() {
+        let initiator_identity = Keypair::generate(&mut RingRng);
+        let responder_identity = Keypair::generate(&mut RingRng);
+
+        let (initiator_state, message1) = init(b"conversation-42");
+        let (responder_state, message2) =
+            respond(&message1, &responder_identity).expect("respond must succeed");
+        let (message3, initiator_completed) =
+            finalize_initiator(&initiator_state, &message2, &initiator_identity)
+                .expect("initiator finalize must succeed");
+        let responder_completed =
+            finalize_responder(&responder_state, &message3).expect("responder finalize must succeed");
+
+        assert_eq!(initiator_completed.transport_key, responder_completed.transport_key);
+        assert_eq!(
+            initiator_completed.peer_identity,
+            responder_identity.public.to_bytes()
+        );
+        assert_eq!(
+            responder_completed.peer_identity,
+            initiator_identity.public.to_bytes()
+        );
+    }
+
+    #[test]
+    fn tampered_message2_is_rejected() {
+        let responder_identity = Keypair::generate(&mut RingRng);
+        let initiator_identity = Keypair::generate(&mut RingRng);
+
+        let (initiator_state, message1) = init(b"conversation-42");
+        let (_responder_state, mut message2) =
+            respond(&message1, &responder_identity).expect("respond must succeed");
+
+        let last = message2.len() - 1;
+        message2[last] ^= 0xff;
+
+        let result = finalize_initiator(&initiator_state, &message2, &initiator_identity);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn tampered_message3_is_rejected() {
+        let responder_identity = Keypair::generate(&mut RingRng);
+        let initiator_identity = Keypair::generate(&mut RingRng);
+
+        let (initiator_state, message1) = init(b"conversation-42");
+        let (responder_state, message2) =
+            respond(&message1, &responder_identity).expect("respond must succeed");
+        let (mut message3, _) = finalize_initiator(&initiator_state, &message2, &initiator_identity)
+            .expect("initiator finalize must succeed");
+
+        let last = message3.len() - 1;
+        message3[last] ^= 0xff;
+
+        let result = finalize_responder(&responder_state, &message3);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn message2_from_a_different_responder_key_reports_that_responders_identity() {
+        let real_responder_identity = Keypair::generate(&mut RingRng);
+        let other_responder_identity = Keypair::generate(&mut RingRng);
+        let initiator_identity = Keypair::generate(&mut RingRng);
+
+        let (initiator_state, message1) = init(b"conversation-42");
+        let (_real_state, _message2) =
+            respond(&message1, &real_responder_identity).expect("respond must succeed");
+
+        // Re-run `respond` for the same message1 with a different responder
+        // identity. The initiator does not pin an expected responder, so its
+        // message2 finalizes, and the completed handshake reports the other
+        // responder's identity rather than the real one.
+        let (_other_state, other_message2) =
+            respond(&message1, &other_responder_identity).expect("respond must succeed");
+
+        let (_, completed) =
+            finalize_initiator(&initiator_state, &other_message2, &initiator_identity)
+                .expect("initiator finalize must succeed");
+        assert_eq!(completed.peer_identity, other_responder_identity.public.to_bytes());
+        assert_ne!(completed.peer_identity, real_responder_identity.public.to_bytes());
+    }
+}
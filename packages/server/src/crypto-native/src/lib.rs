@@ -1,11 +1,22 @@
+mod bip32;
+mod der;
+mod handshake;
+mod rand_compat;
+mod secp256k1_ecdsa;
+mod secret_key;
+
 use neon::prelude::*;
+use neon::types::JsBox;
 use ring::{aead, pbkdf2, rand};
 use x25519_dalek::{EphemeralSecret, PublicKey};
 use ed25519_dalek::{Keypair, Signature, Signer, Verifier};
 use hkdf::Hkdf;
 use sha2::Sha256;
 use aes_gcm::{Aes256Gcm, Key, Nonce, aead::{Aead, NewAead}};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
 use std::num::NonZeroU32;
+use secret_key::SecretKey;
+use zeroize::Zeroize;
 
 /// Generate X25519 key pair for ECDH
 fn generate_x25519_keypair(mut cx: FunctionContext) -> JsResult<JsObject> {
@@ -54,33 +65,31 @@ fn generate_ed25519_keypair(mut cx: FunctionContext) -> JsResult<JsObject> {
     Ok(result)
 }
 
-/// Perform X25519 ECDH key exchange
-fn x25519_ecdh(mut cx: FunctionContext) -> JsResult<JsBuffer> {
-    let secret_buffer = cx.argument::<JsBuffer>(0)?;
+/// Perform X25519 ECDH key exchange, boxing the shared secret as a `SecretKey`
+fn x25519_ecdh(mut cx: FunctionContext) -> JsResult<JsBox<SecretKey>> {
+    let secret_handle = cx.argument::<JsBox<SecretKey>>(0)?;
     let public_buffer = cx.argument::<JsBuffer>(1)?;
-    
-    let secret_bytes = cx.borrow(&secret_buffer, |data| {
+
+    let secret_bytes = match secret_handle.use_bytes(|b| {
         let mut bytes = [0u8; 32];
-        bytes.copy_from_slice(data.as_slice());
+        bytes.copy_from_slice(b);
         bytes
-    });
-    
+    }) {
+        Some(bytes) => bytes,
+        None => return cx.throw_error("SecretKey has been destroyed"),
+    };
+
     let public_bytes = cx.borrow(&public_buffer, |data| {
         let mut bytes = [0u8; 32];
         bytes.copy_from_slice(data.as_slice());
         bytes
     });
-    
+
     let secret = EphemeralSecret::from(secret_bytes);
     let public = PublicKey::from(public_bytes);
     let shared_secret = secret.diffie_hellman(&public);
-    
-    let result = cx.buffer(32)?;
-    cx.borrow_mut(&result, |data| {
-        data.as_mut_slice().copy_from_slice(shared_secret.as_bytes());
-    });
-    
-    Ok(result)
+
+    Ok(cx.boxed(SecretKey::new(shared_secret.as_bytes().to_vec())))
 }
 
 /// HKDF key derivation
@@ -108,19 +117,23 @@ fn hkdf_expand(mut cx: FunctionContext) -> JsResult<JsBuffer> {
     Ok(result)
 }
 
-/// AES-256-GCM encryption
+/// AES-256-GCM encryption. Callers must track a unique 12-byte nonce
+/// themselves; prefer `sealMessage` for new code.
 fn aes_encrypt(mut cx: FunctionContext) -> JsResult<JsObject> {
-    let key_buffer = cx.argument::<JsBuffer>(0)?;
+    let key_handle = cx.argument::<JsBox<SecretKey>>(0)?;
     let nonce_buffer = cx.argument::<JsBuffer>(1)?;
     let plaintext_buffer = cx.argument::<JsBuffer>(2)?;
     let aad_buffer = cx.argument::<JsBuffer>(3)?;
-    
-    let key_bytes = cx.borrow(&key_buffer, |data| {
+
+    let key_bytes = match key_handle.use_bytes(|b| {
         let mut bytes = [0u8; 32];
-        bytes.copy_from_slice(data.as_slice());
+        bytes.copy_from_slice(b);
         bytes
-    });
-    
+    }) {
+        Some(bytes) => bytes,
+        None => return cx.throw_error("SecretKey has been destroyed"),
+    };
+
     let nonce_bytes = cx.borrow(&nonce_buffer, |data| {
         let mut bytes = [0u8; 12];
         bytes.copy_from_slice(data.as_slice());
@@ -149,19 +162,22 @@ fn aes_encrypt(mut cx: FunctionContext) -> JsResult<JsObject> {
     Ok(result)
 }
 
-/// AES-256-GCM decryption
+/// AES-256-GCM decryption. Kept for interop; prefer `openMessage` for new code.
 fn aes_decrypt(mut cx: FunctionContext) -> JsResult<JsBuffer> {
-    let key_buffer = cx.argument::<JsBuffer>(0)?;
+    let key_handle = cx.argument::<JsBox<SecretKey>>(0)?;
     let nonce_buffer = cx.argument::<JsBuffer>(1)?;
     let ciphertext_buffer = cx.argument::<JsBuffer>(2)?;
     let aad_buffer = cx.argument::<JsBuffer>(3)?;
-    
-    let key_bytes = cx.borrow(&key_buffer, |data| {
+
+    let key_bytes = match key_handle.use_bytes(|b| {
         let mut bytes = [0u8; 32];
-        bytes.copy_from_slice(data.as_slice());
+        bytes.copy_from_slice(b);
         bytes
-    });
-    
+    }) {
+        Some(bytes) => bytes,
+        None => return cx.throw_error("SecretKey has been destroyed"),
+    };
+
     let nonce_bytes = cx.borrow(&nonce_buffer, |data| {
         let mut bytes = [0u8; 12];
         bytes.copy_from_slice(data.as_slice());
@@ -188,15 +204,18 @@ fn aes_decrypt(mut cx: FunctionContext) -> JsResult<JsBuffer> {
 
 /// Ed25519 signature creation
 fn ed25519_sign(mut cx: FunctionContext) -> JsResult<JsBuffer> {
-    let keypair_buffer = cx.argument::<JsBuffer>(0)?;
+    let keypair_handle = cx.argument::<JsBox<SecretKey>>(0)?;
     let message_buffer = cx.argument::<JsBuffer>(1)?;
-    
-    let keypair_bytes = cx.borrow(&keypair_buffer, |data| {
+
+    let keypair_bytes = match keypair_handle.use_bytes(|b| {
         let mut bytes = [0u8; 64];
-        bytes.copy_from_slice(data.as_slice());
+        bytes.copy_from_slice(b);
         bytes
-    });
-    
+    }) {
+        Some(bytes) => bytes,
+        None => return cx.throw_error("SecretKey has been destroyed"),
+    };
+
     let message = cx.borrow(&message_buffer, |data| data.as_slice().to_vec());
     
     let keypair = Keypair::from_bytes(&keypair_bytes)
@@ -271,6 +290,643 @@ fn pbkdf2_derive(mut cx: FunctionContext) -> JsResult<JsBuffer> {
     Ok(result)
 }
 
+/// Derive a BIP32-Ed25519 child extended key from a parent extended key.
+///
+/// `parentExtendedKey` is a 96-byte buffer (`kL (32) || kR (32) || chainCode (32)`).
+/// Hardened indices are `>= 2^31`. Returns the child's 96-byte extended key, its
+/// 32-byte public key, and its 64-byte `expandedSecretKey` (`kL || kR`) — sign with
+/// this via `ed25519SignExpanded`, not `ed25519Sign`, since there is no 32-byte seed
+/// to re-hash the way `Keypair::sign` expects.
+fn derive_ed25519_child(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let parent_buffer = cx.argument::<JsBuffer>(0)?;
+    let index = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+
+    let parent_bytes = cx.borrow(&parent_buffer, |data| {
+        let mut bytes = [0u8; 96];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let parent = bip32::ExtendedSecretKey::from_bytes(&parent_bytes);
+    let child = bip32::derive_child(&parent, index);
+
+    let result = cx.empty_object();
+
+    let extended_key_buffer = cx.buffer(96)?;
+    cx.borrow_mut(&extended_key_buffer, |data| {
+        data.as_mut_slice()[0..32].copy_from_slice(&child.kl);
+        data.as_mut_slice()[32..64].copy_from_slice(&child.kr);
+        data.as_mut_slice()[64..96].copy_from_slice(&child.chain_code);
+    });
+
+    let public_key_buffer = cx.buffer(32)?;
+    cx.borrow_mut(&public_key_buffer, |data| {
+        data.as_mut_slice().copy_from_slice(&child.public_key());
+    });
+
+    let expanded_secret_key_buffer = cx.buffer(64)?;
+    cx.borrow_mut(&expanded_secret_key_buffer, |data| {
+        data.as_mut_slice().copy_from_slice(&child.expanded_secret_bytes());
+    });
+
+    result.set(&mut cx, "extendedKey", extended_key_buffer)?;
+    result.set(&mut cx, "publicKey", public_key_buffer)?;
+    result.set(&mut cx, "expandedSecretKey", expanded_secret_key_buffer)?;
+
+    Ok(result)
+}
+
+/// Sign with a child key's 64-byte `expandedSecretKey` (`kL || kR`) from
+/// `deriveEd25519Child`. Plain `ed25519Sign` cannot be used here since there is
+/// no 32-byte seed to re-hash.
+fn ed25519_sign_expanded(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let expanded_secret_key_buffer = cx.argument::<JsBuffer>(0)?;
+    let public_key_buffer = cx.argument::<JsBuffer>(1)?;
+    let message_buffer = cx.argument::<JsBuffer>(2)?;
+
+    let expanded_secret_key_bytes = cx.borrow(&expanded_secret_key_buffer, |data| {
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+    let public_key_bytes = cx.borrow(&public_key_buffer, |data| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+    let message = cx.borrow(&message_buffer, |data| data.as_slice().to_vec());
+
+    let signature = bip32::sign_with_expanded_key(&expanded_secret_key_bytes, &public_key_bytes, &message)
+        .map_err(|_| cx.throw_error("Invalid expanded secret key or public key"))?;
+
+    let result = cx.buffer(64)?;
+    cx.borrow_mut(&result, |data| data.as_mut_slice().copy_from_slice(&signature));
+    Ok(result)
+}
+
+/// Export an Ed25519 keypair's 32-byte seed as a PKCS#8 DER document.
+fn export_ed25519_pkcs8(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let keypair_buffer = cx.argument::<JsBuffer>(0)?;
+    let seed = cx.borrow(&keypair_buffer, |data| data.as_slice()[0..32].to_vec());
+
+    let encoded = der::encode_pkcs8(&der::OID_ED25519, &seed);
+    let result = cx.buffer(encoded.len())?;
+    cx.borrow_mut(&result, |data| data.as_mut_slice().copy_from_slice(&encoded));
+    Ok(result)
+}
+
+/// Export an Ed25519 public key as a SubjectPublicKeyInfo DER document.
+fn export_ed25519_spki(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let public_key_buffer = cx.argument::<JsBuffer>(0)?;
+    let public_key = cx.borrow(&public_key_buffer, |data| data.as_slice().to_vec());
+
+    let encoded = der::encode_spki(&der::OID_ED25519, &public_key);
+    let result = cx.buffer(encoded.len())?;
+    cx.borrow_mut(&result, |data| data.as_mut_slice().copy_from_slice(&encoded));
+    Ok(result)
+}
+
+/// Import an Ed25519 PKCS#8 DER document, re-deriving the public key from the seed.
+fn import_ed25519_pkcs8(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let der_buffer = cx.argument::<JsBuffer>(0)?;
+    let der_bytes = cx.borrow(&der_buffer, |data| data.as_slice().to_vec());
+
+    let seed = der::decode_pkcs8(&der_bytes, &der::OID_ED25519)
+        .map_err(|_| cx.throw_error("Invalid Ed25519 PKCS#8 document"))?;
+
+    let secret = ed25519_dalek::SecretKey::from_bytes(&seed)
+        .map_err(|_| cx.throw_error("Invalid Ed25519 seed"))?;
+    let public: ed25519_dalek::PublicKey = (&secret).into();
+
+    let result = cx.empty_object();
+
+    let secret_key_buffer = cx.buffer(64)?;
+    cx.borrow_mut(&secret_key_buffer, |data| {
+        data.as_mut_slice()[0..32].copy_from_slice(&seed);
+        data.as_mut_slice()[32..64].copy_from_slice(public.as_bytes());
+    });
+
+    let public_key_buffer = cx.buffer(32)?;
+    cx.borrow_mut(&public_key_buffer, |data| {
+        data.as_mut_slice().copy_from_slice(public.as_bytes());
+    });
+
+    result.set(&mut cx, "secretKey", secret_key_buffer)?;
+    result.set(&mut cx, "publicKey", public_key_buffer)?;
+    Ok(result)
+}
+
+/// Import an Ed25519 SubjectPublicKeyInfo DER document.
+fn import_ed25519_spki(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let der_buffer = cx.argument::<JsBuffer>(0)?;
+    let der_bytes = cx.borrow(&der_buffer, |data| data.as_slice().to_vec());
+
+    let public_key = der::decode_spki(&der_bytes, &der::OID_ED25519)
+        .map_err(|_| cx.throw_error("Invalid Ed25519 SPKI document"))?;
+
+    let result = cx.buffer(public_key.len())?;
+    cx.borrow_mut(&result, |data| data.as_mut_slice().copy_from_slice(&public_key));
+    Ok(result)
+}
+
+/// Export an X25519 secret key as a PKCS#8 DER document.
+fn export_x25519_pkcs8(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let secret_key_buffer = cx.argument::<JsBuffer>(0)?;
+    let secret_key = cx.borrow(&secret_key_buffer, |data| data.as_slice().to_vec());
+
+    let encoded = der::encode_pkcs8(&der::OID_X25519, &secret_key);
+    let result = cx.buffer(encoded.len())?;
+    cx.borrow_mut(&result, |data| data.as_mut_slice().copy_from_slice(&encoded));
+    Ok(result)
+}
+
+/// Export an X25519 public key as a SubjectPublicKeyInfo DER document.
+fn export_x25519_spki(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let public_key_buffer = cx.argument::<JsBuffer>(0)?;
+    let public_key = cx.borrow(&public_key_buffer, |data| data.as_slice().to_vec());
+
+    let encoded = der::encode_spki(&der::OID_X25519, &public_key);
+    let result = cx.buffer(encoded.len())?;
+    cx.borrow_mut(&result, |data| data.as_mut_slice().copy_from_slice(&encoded));
+    Ok(result)
+}
+
+/// Import an X25519 PKCS#8 DER document.
+fn import_x25519_pkcs8(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let der_buffer = cx.argument::<JsBuffer>(0)?;
+    let der_bytes = cx.borrow(&der_buffer, |data| data.as_slice().to_vec());
+
+    let secret_key = der::decode_pkcs8(&der_bytes, &der::OID_X25519)
+        .map_err(|_| cx.throw_error("Invalid X25519 PKCS#8 document"))?;
+
+    let result = cx.buffer(secret_key.len())?;
+    cx.borrow_mut(&result, |data| data.as_mut_slice().copy_from_slice(&secret_key));
+    Ok(result)
+}
+
+/// Import an X25519 SubjectPublicKeyInfo DER document.
+fn import_x25519_spki(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let der_buffer = cx.argument::<JsBuffer>(0)?;
+    let der_bytes = cx.borrow(&der_buffer, |data| data.as_slice().to_vec());
+
+    let public_key = der::decode_spki(&der_bytes, &der::OID_X25519)
+        .map_err(|_| cx.throw_error("Invalid X25519 SPKI document"))?;
+
+    let result = cx.buffer(public_key.len())?;
+    cx.borrow_mut(&result, |data| data.as_mut_slice().copy_from_slice(&public_key));
+    Ok(result)
+}
+
+/// Generate an X25519 key pair, returning the secret half as a zero-on-free
+/// `SecretKey` handle instead of a plain `Buffer`.
+fn generate_x25519_keypair_secret(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let secret = EphemeralSecret::new(&mut rand::SystemRandom::new());
+    let public = PublicKey::from(&secret);
+
+    let result = cx.empty_object();
+    let secret_handle = cx.boxed(SecretKey::new(secret.to_bytes().to_vec()));
+
+    let public_bytes = cx.buffer(32)?;
+    cx.borrow_mut(&public_bytes, |data| {
+        data.as_mut_slice().copy_from_slice(public.as_bytes());
+    });
+
+    result.set(&mut cx, "secretKey", secret_handle)?;
+    result.set(&mut cx, "publicKey", public_bytes)?;
+
+    Ok(result)
+}
+
+/// Generate an Ed25519 key pair, returning the secret half as a zero-on-free
+/// `SecretKey` handle instead of a plain `Buffer`.
+fn generate_ed25519_keypair_secret(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let mut csprng = rand::SystemRandom::new();
+    let keypair = Keypair::generate(&mut csprng);
+
+    let result = cx.empty_object();
+    let secret_handle = cx.boxed(SecretKey::new(keypair.to_bytes().to_vec()));
+
+    let public_bytes = cx.buffer(32)?;
+    cx.borrow_mut(&public_bytes, |data| {
+        data.as_mut_slice().copy_from_slice(keypair.public.as_bytes());
+    });
+
+    result.set(&mut cx, "secretKey", secret_handle)?;
+    result.set(&mut cx, "publicKey", public_bytes)?;
+
+    Ok(result)
+}
+
+/// HKDF key derivation, returning the output keying material as a zero-on-free
+/// `SecretKey` handle instead of a plain `Buffer`.
+fn hkdf_expand_secret(mut cx: FunctionContext) -> JsResult<JsBox<SecretKey>> {
+    let salt_buffer = cx.argument::<JsBuffer>(0)?;
+    let ikm_buffer = cx.argument::<JsBuffer>(1)?;
+    let info_buffer = cx.argument::<JsBuffer>(2)?;
+    let length = cx.argument::<JsNumber>(3)?.value(&mut cx) as usize;
+
+    let salt = cx.borrow(&salt_buffer, |data| data.as_slice().to_vec());
+    let ikm = cx.borrow(&ikm_buffer, |data| data.as_slice().to_vec());
+    let info = cx.borrow(&info_buffer, |data| data.as_slice().to_vec());
+
+    let hk = Hkdf::<Sha256>::new(Some(&salt), &ikm);
+    let mut okm = vec![0u8; length];
+    hk.expand(&info, &mut okm).map_err(|_| {
+        cx.throw_error("HKDF expansion failed")
+    })?;
+
+    Ok(cx.boxed(SecretKey::new(okm)))
+}
+
+/// PBKDF2 key derivation, returning the derived key as a zero-on-free
+/// `SecretKey` handle instead of a plain `Buffer`.
+fn pbkdf2_derive_secret(mut cx: FunctionContext) -> JsResult<JsBox<SecretKey>> {
+    let password_buffer = cx.argument::<JsBuffer>(0)?;
+    let salt_buffer = cx.argument::<JsBuffer>(1)?;
+    let iterations = cx.argument::<JsNumber>(2)?.value(&mut cx) as u32;
+    let output_length = cx.argument::<JsNumber>(3)?.value(&mut cx) as usize;
+
+    let password = cx.borrow(&password_buffer, |data| data.as_slice().to_vec());
+    let salt = cx.borrow(&salt_buffer, |data| data.as_slice().to_vec());
+
+    let mut output = vec![0u8; output_length];
+
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(iterations).unwrap(),
+        &salt,
+        &password,
+        &mut output,
+    );
+
+    Ok(cx.boxed(SecretKey::new(output)))
+}
+
+/// Borrow a `SecretKey`'s bytes transiently, passing a copy to a JS callback
+/// and wiping that copy again once the callback returns.
+fn secret_key_use(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let handle = cx.argument::<JsBox<SecretKey>>(0)?;
+    let callback = cx.argument::<JsFunction>(1)?;
+
+    let mut bytes = match handle.use_bytes(|b| b.to_vec()) {
+        Some(bytes) => bytes,
+        None => return cx.throw_error("SecretKey has been destroyed"),
+    };
+
+    let buffer = cx.buffer(bytes.len())?;
+    cx.borrow_mut(&buffer, |data| data.as_mut_slice().copy_from_slice(&bytes));
+    bytes.zeroize();
+
+    let this = cx.undefined();
+    let args = vec![buffer.upcast::<JsValue>()];
+    let result = callback.call(&mut cx, this, args);
+
+    cx.borrow_mut(&buffer, |data| data.as_mut_slice().zeroize());
+
+    result
+}
+
+/// Wipe a `SecretKey`'s bytes immediately rather than waiting for it to be
+/// garbage collected.
+fn secret_key_destroy(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let handle = cx.argument::<JsBox<SecretKey>>(0)?;
+    handle.destroy();
+    Ok(cx.undefined())
+}
+
+/// Promote a raw `Buffer` (e.g. from `generateEd25519Keypair` or `importEd25519Pkcs8`)
+/// into a `SecretKey` handle so it can be passed to `x25519Ecdh`,
+/// `aesEncrypt`/`aesDecrypt`, or `ed25519Sign`.
+fn secret_key_from_buffer(mut cx: FunctionContext) -> JsResult<JsBox<SecretKey>> {
+    let buffer = cx.argument::<JsBuffer>(0)?;
+    let bytes = cx.borrow(&buffer, |data| data.as_slice().to_vec());
+    Ok(cx.boxed(SecretKey::new(bytes)))
+}
+
+/// Encrypt with XChaCha20-Poly1305 using a fresh random 24-byte nonce,
+/// prepending the nonce to the ciphertext+tag.
+fn seal_message(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let key_handle = cx.argument::<JsBox<SecretKey>>(0)?;
+    let plaintext_buffer = cx.argument::<JsBuffer>(1)?;
+    let aad_buffer = cx.argument::<JsBuffer>(2)?;
+
+    let key_bytes = match key_handle.use_bytes(|b| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(b);
+        bytes
+    }) {
+        Some(bytes) => bytes,
+        None => return cx.throw_error("SecretKey has been destroyed"),
+    };
+
+    let plaintext = cx.borrow(&plaintext_buffer, |data| data.as_slice().to_vec());
+    let aad = cx.borrow(&aad_buffer, |data| data.as_slice().to_vec());
+
+    let mut nonce_bytes = [0u8; 24];
+    rand::SecureRandom::fill(&rand::SystemRandom::new(), &mut nonce_bytes)
+        .map_err(|_| cx.throw_error("Failed to generate nonce"))?;
+
+    let key = chacha20poly1305::Key::from_slice(&key_bytes);
+    let cipher = XChaCha20Poly1305::new(key);
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    let payload = chacha20poly1305::aead::Payload {
+        msg: &plaintext,
+        aad: &aad,
+    };
+    let ciphertext = cipher.encrypt(nonce, payload)
+        .map_err(|_| cx.throw_error("Encryption failed"))?;
+
+    let result = cx.buffer(24 + ciphertext.len())?;
+    cx.borrow_mut(&result, |data| {
+        data.as_mut_slice()[0..24].copy_from_slice(&nonce_bytes);
+        data.as_mut_slice()[24..].copy_from_slice(&ciphertext);
+    });
+
+    Ok(result)
+}
+
+/// Open a message sealed with `sealMessage`: splits off the leading 24-byte
+/// nonce before verifying the Poly1305 tag.
+fn open_message(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let key_handle = cx.argument::<JsBox<SecretKey>>(0)?;
+    let sealed_buffer = cx.argument::<JsBuffer>(1)?;
+    let aad_buffer = cx.argument::<JsBuffer>(2)?;
+
+    let key_bytes = match key_handle.use_bytes(|b| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(b);
+        bytes
+    }) {
+        Some(bytes) => bytes,
+        None => return cx.throw_error("SecretKey has been destroyed"),
+    };
+
+    let sealed = cx.borrow(&sealed_buffer, |data| data.as_slice().to_vec());
+    let aad = cx.borrow(&aad_buffer, |data| data.as_slice().to_vec());
+
+    if sealed.len() < 24 {
+        return cx.throw_error("Sealed message is too short to contain a nonce");
+    }
+    let (nonce_bytes, ciphertext) = sealed.split_at(24);
+
+    let key = chacha20poly1305::Key::from_slice(&key_bytes);
+    let cipher = XChaCha20Poly1305::new(key);
+    let nonce = XNonce::from_slice(nonce_bytes);
+
+    let payload = chacha20poly1305::aead::Payload {
+        msg: ciphertext,
+        aad: &aad,
+    };
+    let plaintext = cipher.decrypt(nonce, payload)
+        .map_err(|_| cx.throw_error("Decryption failed"))?;
+
+    let result = cx.buffer(plaintext.len())?;
+    cx.borrow_mut(&result, |data| {
+        data.as_mut_slice().copy_from_slice(&plaintext);
+    });
+
+    Ok(result)
+}
+
+/// Generate a secp256k1 key pair for Ethereum-style wallet-linked identities.
+fn generate_secp256k1_keypair(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let (secret_key_bytes, public_key_bytes) = secp256k1_ecdsa::generate_keypair();
+
+    let result = cx.empty_object();
+
+    let secret_key_buffer = cx.buffer(32)?;
+    cx.borrow_mut(&secret_key_buffer, |data| {
+        data.as_mut_slice().copy_from_slice(&secret_key_bytes);
+    });
+
+    let public_key_buffer = cx.buffer(64)?;
+    cx.borrow_mut(&public_key_buffer, |data| {
+        data.as_mut_slice().copy_from_slice(&public_key_bytes);
+    });
+
+    result.set(&mut cx, "secretKey", secret_key_buffer)?;
+    result.set(&mut cx, "publicKey", public_key_buffer)?;
+
+    Ok(result)
+}
+
+/// Derive the 64-byte uncompressed public key from a 32-byte secp256k1 secret key.
+fn secp256k1_public_key_from_secret(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let secret_key_buffer = cx.argument::<JsBuffer>(0)?;
+
+    let secret_key_bytes = cx.borrow(&secret_key_buffer, |data| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let public_key = secp256k1_ecdsa::public_key_from_secret(&secret_key_bytes)
+        .map_err(|_| cx.throw_error("Invalid secp256k1 secret key"))?;
+
+    let result = cx.buffer(64)?;
+    cx.borrow_mut(&result, |data| {
+        data.as_mut_slice().copy_from_slice(&public_key);
+    });
+
+    Ok(result)
+}
+
+/// Sign a 32-byte message hash with secp256k1 ECDSA, returning a 65-byte
+/// `r (32) || s (32) || v (1)` recoverable signature with low-S normalization.
+fn secp256k1_sign(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let message_hash_buffer = cx.argument::<JsBuffer>(0)?;
+    let secret_key_buffer = cx.argument::<JsBuffer>(1)?;
+
+    let message_hash = cx.borrow(&message_hash_buffer, |data| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let secret_key_bytes = cx.borrow(&secret_key_buffer, |data| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let signature = secp256k1_ecdsa::sign(&message_hash, &secret_key_bytes)
+        .map_err(|_| cx.throw_error("secp256k1 signing failed"))?;
+
+    let result = cx.buffer(65)?;
+    cx.borrow_mut(&result, |data| {
+        data.as_mut_slice().copy_from_slice(&signature);
+    });
+
+    Ok(result)
+}
+
+/// Recover the 64-byte uncompressed public key from a 32-byte message hash
+/// and a 65-byte `r || s || v` signature.
+fn secp256k1_recover(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let message_hash_buffer = cx.argument::<JsBuffer>(0)?;
+    let signature_buffer = cx.argument::<JsBuffer>(1)?;
+
+    let message_hash = cx.borrow(&message_hash_buffer, |data| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let signature = cx.borrow(&signature_buffer, |data| {
+        let mut bytes = [0u8; 65];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let public_key = secp256k1_ecdsa::recover(&message_hash, &signature)
+        .map_err(|_| cx.throw_error("secp256k1 signature recovery failed"))?;
+
+    let result = cx.buffer(64)?;
+    cx.borrow_mut(&result, |data| {
+        data.as_mut_slice().copy_from_slice(&public_key);
+    });
+
+    Ok(result)
+}
+
+/// Verify a secp256k1 `r || s || v` signature against a claimed 64-byte
+/// uncompressed public key.
+fn secp256k1_verify(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let message_hash_buffer = cx.argument::<JsBuffer>(0)?;
+    let signature_buffer = cx.argument::<JsBuffer>(1)?;
+    let public_key_buffer = cx.argument::<JsBuffer>(2)?;
+
+    let message_hash = cx.borrow(&message_hash_buffer, |data| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let signature = cx.borrow(&signature_buffer, |data| {
+        let mut bytes = [0u8; 65];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let public_key = cx.borrow(&public_key_buffer, |data| {
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+
+    let is_valid = secp256k1_ecdsa::verify(&message_hash, &signature, &public_key);
+
+    Ok(cx.boolean(is_valid))
+}
+
+/// Message 1: start a handshake as the initiator. Returns a boxed handshake
+/// state (to be threaded into `handshakeFinalize`) and the message to send.
+fn handshake_init(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let connection_id_buffer = cx.argument::<JsBuffer>(0)?;
+    let connection_id = cx.borrow(&connection_id_buffer, |data| data.as_slice().to_vec());
+
+    let (state, message1) = handshake::init(&connection_id);
+
+    let result = cx.empty_object();
+    let state_handle = cx.boxed(state);
+
+    let message1_buffer = cx.buffer(message1.len())?;
+    cx.borrow_mut(&message1_buffer, |data| data.as_mut_slice().copy_from_slice(&message1));
+
+    result.set(&mut cx, "state", state_handle)?;
+    result.set(&mut cx, "message1", message1_buffer)?;
+    Ok(result)
+}
+
+/// Message 2: respond to an initiator's message 1. `responderIdentityKeypair`
+/// is the responder's 64-byte ed25519-dalek keypair bytes. Returns a boxed
+/// handshake state (to be threaded into `handshakeFinalize`) and the message
+/// to send back.
+fn handshake_respond(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let message1_buffer = cx.argument::<JsBuffer>(0)?;
+    let identity_buffer = cx.argument::<JsBuffer>(1)?;
+
+    let message1 = cx.borrow(&message1_buffer, |data| data.as_slice().to_vec());
+    let identity_bytes = cx.borrow(&identity_buffer, |data| {
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(data.as_slice());
+        bytes
+    });
+    let identity = Keypair::from_bytes(&identity_bytes)
+        .map_err(|_| cx.throw_error("Invalid responder identity keypair"))?;
+
+    let (state, message2) = handshake::respond(&message1, &identity)
+        .map_err(|_| cx.throw_error("Handshake message 1 rejected"))?;
+
+    let result = cx.empty_object();
+    let state_handle = cx.boxed(state);
+
+    let message2_buffer = cx.buffer(message2.len())?;
+    cx.borrow_mut(&message2_buffer, |data| data.as_mut_slice().copy_from_slice(&message2));
+
+    result.set(&mut cx, "state", state_handle)?;
+    result.set(&mut cx, "message2", message2_buffer)?;
+    Ok(result)
+}
+
+/// Message 3 and completion. On the initiator side (state awaiting message 2)
+/// this takes message 2 plus the initiator's 64-byte identity keypair and
+/// returns `{ message3, transportKey, peerIdentity }`. On the responder side
+/// (state awaiting message 3) this takes message 3 and no identity keypair is
+/// needed, returning `{ transportKey, peerIdentity }`. `transportKey` is a
+/// boxed `SecretKey` handle rather than a plain `Buffer`, since it is the
+/// session key for everything that follows. Rejects on any signature or
+/// AEAD-tag failure.
+fn handshake_finalize(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let state_handle = cx.argument::<JsBox<handshake::HandshakeState>>(0)?;
+    let message_buffer = cx.argument::<JsBuffer>(1)?;
+    let message = cx.borrow(&message_buffer, |data| data.as_slice().to_vec());
+
+    let awaiting_message2 = match state_handle.is_awaiting_message2() {
+        Some(flag) => flag,
+        None => return cx.throw_error("Handshake state already consumed"),
+    };
+
+    let result = cx.empty_object();
+
+    if awaiting_message2 {
+        let identity_buffer = cx.argument::<JsBuffer>(2)?;
+        let identity_bytes = cx.borrow(&identity_buffer, |data| {
+            let mut bytes = [0u8; 64];
+            bytes.copy_from_slice(data.as_slice());
+            bytes
+        });
+        let identity = Keypair::from_bytes(&identity_bytes)
+            .map_err(|_| cx.throw_error("Invalid initiator identity keypair"))?;
+
+        let (message3, completed) = handshake::finalize_initiator(&state_handle, &message, &identity)
+            .map_err(|_| cx.throw_error("Handshake message 2 rejected"))?;
+
+        let message3_buffer = cx.buffer(message3.len())?;
+        cx.borrow_mut(&message3_buffer, |data| data.as_mut_slice().copy_from_slice(&message3));
+        let transport_key_handle = cx.boxed(SecretKey::new(completed.transport_key.to_vec()));
+        let peer_identity_buffer = cx.buffer(32)?;
+        cx.borrow_mut(&peer_identity_buffer, |data| data.as_mut_slice().copy_from_slice(&completed.peer_identity));
+
+        result.set(&mut cx, "message3", message3_buffer)?;
+        result.set(&mut cx, "transportKey", transport_key_handle)?;
+        result.set(&mut cx, "peerIdentity", peer_identity_buffer)?;
+    } else {
+        let completed = handshake::finalize_responder(&state_handle, &message)
+            .map_err(|_| cx.throw_error("Handshake message 3 rejected"))?;
+
+        let transport_key_handle = cx.boxed(SecretKey::new(completed.transport_key.to_vec()));
+        let peer_identity_buffer = cx.buffer(32)?;
+        cx.borrow_mut(&peer_identity_buffer, |data| data.as_mut_slice().copy_from_slice(&completed.peer_identity));
+
+        result.set(&mut cx, "transportKey", transport_key_handle)?;
+        result.set(&mut cx, "peerIdentity", peer_identity_buffer)?;
+    }
+
+    Ok(result)
+}
+
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("generateX25519Keypair", generate_x25519_keypair)?;
@@ -282,5 +938,32 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("ed25519Sign", ed25519_sign)?;
     cx.export_function("ed25519Verify", ed25519_verify)?;
     cx.export_function("pbkdf2Derive", pbkdf2_derive)?;
+    cx.export_function("deriveEd25519Child", derive_ed25519_child)?;
+    cx.export_function("ed25519SignExpanded", ed25519_sign_expanded)?;
+    cx.export_function("exportEd25519Pkcs8", export_ed25519_pkcs8)?;
+    cx.export_function("exportEd25519Spki", export_ed25519_spki)?;
+    cx.export_function("importEd25519Pkcs8", import_ed25519_pkcs8)?;
+    cx.export_function("importEd25519Spki", import_ed25519_spki)?;
+    cx.export_function("exportX25519Pkcs8", export_x25519_pkcs8)?;
+    cx.export_function("exportX25519Spki", export_x25519_spki)?;
+    cx.export_function("importX25519Pkcs8", import_x25519_pkcs8)?;
+    cx.export_function("importX25519Spki", import_x25519_spki)?;
+    cx.export_function("generateX25519KeypairSecret", generate_x25519_keypair_secret)?;
+    cx.export_function("generateEd25519KeypairSecret", generate_ed25519_keypair_secret)?;
+    cx.export_function("hkdfExpandSecret", hkdf_expand_secret)?;
+    cx.export_function("pbkdf2DeriveSecret", pbkdf2_derive_secret)?;
+    cx.export_function("secretKeyUse", secret_key_use)?;
+    cx.export_function("secretKeyDestroy", secret_key_destroy)?;
+    cx.export_function("secretKeyFromBuffer", secret_key_from_buffer)?;
+    cx.export_function("sealMessage", seal_message)?;
+    cx.export_function("openMessage", open_message)?;
+    cx.export_function("generateSecp256k1Keypair", generate_secp256k1_keypair)?;
+    cx.export_function("secp256k1PublicKeyFromSecret", secp256k1_public_key_from_secret)?;
+    cx.export_function("secp256k1Sign", secp256k1_sign)?;
+    cx.export_function("secp256k1Recover", secp256k1_recover)?;
+    cx.export_function("secp256k1Verify", secp256k1_verify)?;
+    cx.export_function("handshakeInit", handshake_init)?;
+    cx.export_function("handshakeRespond", handshake_respond)?;
+    cx.export_function("handshakeFinalize", handshake_finalize)?;
     Ok(())
 }
@@ -0,0 +1,33 @@
+//! Minimal adapter so crates expecting a `rand_core::RngCore` (secp256k1,
+//! x25519-dalek) can draw from `ring`'s CSPRNG, matching the `SystemRandom`
+//! source used elsewhere in this crate.
+
+use rand_core::{CryptoRng, RngCore};
+use ring::rand::{SecureRandom, SystemRandom};
+
+pub struct RingRng;
+
+impl RngCore for RingRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        SystemRandom::new().fill(dest).expect("system RNG failure");
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for RingRng {}
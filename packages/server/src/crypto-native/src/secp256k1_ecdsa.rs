@@ -0,0 +1,107 @@
+//! secp256k1 ECDSA with recoverable `(r, s, v)` signatures, for Ethereum-style
+//! wallet-linked identities. Mirrors the R/S/V signature model used by the
+//! secp256k1/ethcrypto stack.
+
+use crate::rand_compat::RingRng;
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+/// Generate a secp256k1 keypair: a random 32-byte secret key and its
+/// corresponding 64-byte uncompressed public key (no `0x04` prefix).
+pub fn generate_keypair() -> ([u8; 32], [u8; 64]) {
+    let secp = Secp256k1::new();
+    let mut rng = RingRng;
+    let secret_key = SecretKey::new(&mut rng);
+    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+    (secret_key.secret_bytes(), uncompressed_64(&public_key))
+}
+
+/// Derive the 64-byte uncompressed public key from a 32-byte secret key.
+pub fn public_key_from_secret(secret_key_bytes: &[u8; 32]) -> Result<[u8; 64], String> {
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(secret_key_bytes).map_err(|e| e.to_string())?;
+    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+    Ok(uncompressed_64(&public_key))
+}
+
+fn uncompressed_64(public_key: &PublicKey) -> [u8; 64] {
+    let serialized = public_key.serialize_uncompressed();
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&serialized[1..65]);
+    out
+}
+
+/// Sign a 32-byte message hash, returning a 65-byte `r (32) || s (32) || v (1)`
+/// signature with low-S normalization already applied by the underlying library.
+pub fn sign(message_hash: &[u8; 32], secret_key_bytes: &[u8; 32]) -> Result<[u8; 65], String> {
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(secret_key_bytes).map_err(|e| e.to_string())?;
+    let message = Message::from_slice(message_hash).map_err(|e| e.to_string())?;
+
+    let recoverable_sig = secp.sign_ecdsa_recoverable(&message, &secret_key);
+    let (recovery_id, compact) = recoverable_sig.serialize_compact();
+
+    let mut out = [0u8; 65];
+    out[0..64].copy_from_slice(&compact);
+    out[64] = recovery_id.to_i32() as u8;
+    Ok(out)
+}
+
+/// Recover the 64-byte uncompressed public key from a 32-byte message hash
+/// and a 65-byte `r || s || v` signature. Accepts `v` as `0`/`1` or `27`/`28`.
+pub fn recover(message_hash: &[u8; 32], signature: &[u8; 65]) -> Result<[u8; 64], String> {
+    let secp = Secp256k1::new();
+    let message = Message::from_slice(message_hash).map_err(|e| e.to_string())?;
+
+    let v = signature[64];
+    let normalized_v = if v >= 27 { v - 27 } else { v };
+    let recovery_id = RecoveryId::from_i32(normalized_v as i32).map_err(|e| e.to_string())?;
+    let recoverable_sig = RecoverableSignature::from_compact(&signature[0..64], recovery_id)
+        .map_err(|e| e.to_string())?;
+
+    let public_key = secp
+        .recover_ecdsa(&message, &recoverable_sig)
+        .map_err(|e| e.to_string())?;
+
+    Ok(uncompressed_64(&public_key))
+}
+
+/// Verify a non-recoverable signature check: re-derive the signer via
+/// recovery and confirm it matches the claimed public key.
+pub fn verify(message_hash: &[u8; 32], signature: &[u8; 65], public_key_bytes: &[u8; 64]) -> bool {
+    match recover(message_hash, signature) {
+        Ok(recovered) => recovered == *public_key_bytes,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_recover_verify_round_trip() {
+        let (secret_key, public_key) = generate_keypair();
+        let message_hash = [42u8; 32];
+
+        let signature = sign(&message_hash, &secret_key).expect("signing must succeed");
+        let recovered = recover(&message_hash, &signature).expect("recovery must succeed");
+
+        assert_eq!(recovered, public_key);
+        assert!(verify(&message_hash, &signature, &public_key));
+    }
+
+    #[test]
+    fn public_key_from_secret_matches_generated_keypair() {
+        let (secret_key, public_key) = generate_keypair();
+        let derived = public_key_from_secret(&secret_key).expect("derivation must succeed");
+        assert_eq!(derived, public_key);
+    }
+
+    #[test]
+    fn verify_rejects_signature_for_a_different_message() {
+        let (secret_key, public_key) = generate_keypair();
+        let signature = sign(&[1u8; 32], &secret_key).expect("signing must succeed");
+        assert!(!verify(&[2u8; 32], &signature, &public_key));
+    }
+}
@@ -0,0 +1,43 @@
+//! A boxed secret-key handle that zeroizes its contents on `Drop`.
+//!
+//! `SecretKey` owns its bytes behind a `RefCell<Option<Vec<u8>>>` so they can
+//! be wiped once, either explicitly via `destroy()` or implicitly when the
+//! handle is dropped, instead of relying on a plain `Buffer` to eventually be
+//! garbage-collected.
+
+use neon::types::Finalize;
+use std::cell::RefCell;
+use zeroize::Zeroize;
+
+pub struct SecretKey {
+    bytes: RefCell<Option<Vec<u8>>>,
+}
+
+impl SecretKey {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        SecretKey {
+            bytes: RefCell::new(Some(bytes)),
+        }
+    }
+
+    /// Borrow the live bytes transiently. Returns `None` if the key has
+    /// already been destroyed.
+    pub fn use_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        self.bytes.borrow().as_ref().map(|b| f(b.as_slice()))
+    }
+
+    /// Wipe the bytes immediately rather than waiting for `Drop`.
+    pub fn destroy(&self) {
+        if let Some(mut bytes) = self.bytes.borrow_mut().take() {
+            bytes.zeroize();
+        }
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.destroy();
+    }
+}
+
+impl Finalize for SecretKey {}